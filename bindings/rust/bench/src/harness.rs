@@ -0,0 +1,182 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{PemType, SigType};
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    error::Error,
+    fs,
+    io::{Read, Write},
+    path::PathBuf,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+/// One in-memory endpoint of a connected buffer pair, used instead of file
+/// descriptors to drive handshakes without touching the network.
+///
+/// `send` and `recv` are each shared with the peer endpoint created by
+/// [`Self::clone_inverse`], with the roles swapped, so this endpoint's
+/// writes land in the queue the peer reads from, and vice versa.
+#[derive(Default, Clone)]
+pub struct ConnectedBuffer {
+    recv: Rc<RefCell<VecDeque<u8>>>,
+    send: Rc<RefCell<VecDeque<u8>>>,
+}
+
+impl ConnectedBuffer {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the other endpoint of this buffer pair: it reads what this
+    /// buffer writes, and vice versa
+    pub fn clone_inverse(&self) -> Self {
+        Self {
+            recv: self.send.clone(),
+            send: self.recv.clone(),
+        }
+    }
+}
+
+impl Read for ConnectedBuffer {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.recv.borrow_mut().read(buf) {
+            // an empty queue means the peer has not written yet, not EOF
+            Ok(0) if !buf.is_empty() => Err(std::io::ErrorKind::WouldBlock.into()),
+            res => res,
+        }
+    }
+}
+
+impl Write for ConnectedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.send.borrow_mut().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Mode {
+    Client,
+    Server,
+}
+
+/// A shared sink for NSS-format `SSLKEYLOGFILE` lines (e.g. `CLIENT_RANDOM
+/// ...`), so a backend's client and server connections can append to the
+/// same writer for later decryption in Wireshark
+pub type KeyLogSink = Arc<Mutex<dyn Write + Send>>;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HandshakeType {
+    ServerAuth,
+    MutualAuth,
+    /// A server-auth handshake that negotiates a PSK via a session ticket
+    /// issued by a prior full handshake, rather than a full key exchange
+    Resumption,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CipherSuite {
+    AES_128_GCM_SHA256,
+    AES_256_GCM_SHA384,
+    CHACHA20_POLY1305_SHA256,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ECGroup {
+    SECP256R1,
+    X25519,
+    /// Classical X25519 combined with the ML-KEM-768 post-quantum KEM
+    X25519MlKem768,
+    /// Classical SECP256R1 combined with the ML-KEM-768 post-quantum KEM
+    SecP256r1MlKem768,
+}
+
+#[derive(Clone, Copy)]
+pub struct CryptoConfig {
+    pub cipher_suite: CipherSuite,
+    pub ec_group: ECGroup,
+    pub sig_type: SigType,
+    /// Staple an OCSP response to the server's certificate and have the
+    /// client verify it during the handshake. Only implemented by the
+    /// s2n-tls backend; [`crate::openssl::OpenSslHarness`] rejects this
+    /// rather than silently skipping stapling.
+    pub ocsp: bool,
+}
+
+impl CryptoConfig {
+    pub fn new(cipher_suite: CipherSuite, ec_group: ECGroup, sig_type: SigType) -> Self {
+        Self {
+            cipher_suite,
+            ec_group,
+            sig_type,
+            ocsp: false,
+        }
+    }
+}
+
+/// Loads a fixture (cert/key/OCSP response) for the given signature type from
+/// the crate's `certs/` fixture directory
+pub fn read_to_bytes(pem_type: PemType, sig_type: SigType) -> Vec<u8> {
+    let extension = match pem_type {
+        PemType::OcspResponse => "der",
+        _ => "pem",
+    };
+    let path: PathBuf = [
+        env!("CARGO_MANIFEST_DIR"),
+        "certs",
+        &format!(
+            "{}_{}.{extension}",
+            sig_type.as_dir_name(),
+            pem_type.as_file_name()
+        ),
+    ]
+    .iter()
+    .collect();
+    fs::read(&path).unwrap_or_else(|err| panic!("failed to read {path:?}: {err}"))
+}
+
+/// Interface that all TLS harnesses implement so criterion benchmarks can be
+/// written generically across backends (s2n-tls, OpenSSL, ...)
+pub trait TlsBenchHarness: Sized {
+    fn new(
+        crypto_config: CryptoConfig,
+        handshake_type: HandshakeType,
+        buffer: ConnectedBuffer,
+    ) -> Result<Self, Box<dyn Error>>;
+
+    /// Like [`Self::new`], but installs `keylog` on every connection so the
+    /// handshake's and session's TLS secrets are appended to it in NSS
+    /// keylog format. Lets a regressed or failing benchmark be captured and
+    /// decrypted for diagnosis.
+    fn new_with_keylog(
+        crypto_config: CryptoConfig,
+        handshake_type: HandshakeType,
+        buffer: ConnectedBuffer,
+        keylog: KeyLogSink,
+    ) -> Result<Self, Box<dyn Error>>;
+
+    /// Run the handshake to completion
+    fn handshake(&mut self) -> Result<(), Box<dyn Error>>;
+
+    /// Returns true if both peers completed the handshake
+    fn handshake_completed(&self) -> bool;
+
+    fn get_negotiated_cipher_suite(&self) -> CipherSuite;
+
+    /// Returns the key-exchange group negotiated during the handshake,
+    /// classical or post-quantum hybrid
+    fn get_negotiated_group(&self) -> ECGroup;
+
+    fn negotiated_tls13(&self) -> bool;
+
+    fn send(&mut self, sender: Mode, data: &[u8]) -> Result<(), Box<dyn Error>>;
+
+    fn recv(&mut self, receiver: Mode, data: &mut [u8]) -> Result<(), Box<dyn Error>>;
+}
@@ -0,0 +1,46 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod harness;
+pub mod openssl;
+pub mod s2n_tls;
+
+pub use harness::*;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PemType {
+    ServerCertChain,
+    ServerKey,
+    ClientCertChain,
+    ClientKey,
+    CACert,
+    OcspResponse,
+}
+
+impl PemType {
+    pub fn as_file_name(&self) -> &'static str {
+        match self {
+            PemType::ServerCertChain => "server-chain",
+            PemType::ServerKey => "server-key",
+            PemType::ClientCertChain => "client-chain",
+            PemType::ClientKey => "client-key",
+            PemType::CACert => "ca-cert",
+            PemType::OcspResponse => "ocsp-response",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SigType {
+    Rsa2048,
+    Ecdsa256,
+}
+
+impl SigType {
+    pub fn as_dir_name(&self) -> &'static str {
+        match self {
+            SigType::Rsa2048 => "rsa2048",
+            SigType::Ecdsa256 => "ecdsa256",
+        }
+    }
+}
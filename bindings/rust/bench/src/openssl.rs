@@ -0,0 +1,321 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    harness::{
+        read_to_bytes, CipherSuite, ConnectedBuffer, CryptoConfig, ECGroup, HandshakeType,
+        KeyLogSink, Mode, TlsBenchHarness,
+    },
+    PemType::*,
+};
+use openssl::{
+    pkey::PKey,
+    ssl::{
+        HandshakeError, MidHandshakeSslStream, Ssl, SslContext, SslContextBuilder, SslMethod,
+        SslStream, SslVerifyMode, SslVersion,
+    },
+    x509::X509,
+};
+use std::{error::Error, io::Write};
+
+/// A connection is either still negotiating or has finished its handshake.
+/// `openssl`'s non-blocking handshake API hands back a `MidHandshakeSslStream`
+/// on `WouldBlock` rather than letting us poll the original stream in place,
+/// so we track which state each side is in and swap between them.
+enum Conn {
+    Handshaking(MidHandshakeSslStream<ConnectedBuffer>),
+    Ready(SslStream<ConnectedBuffer>),
+}
+
+impl Conn {
+    fn stream(&self) -> &SslStream<ConnectedBuffer> {
+        match self {
+            Conn::Handshaking(mid) => mid.ssl_stream(),
+            Conn::Ready(stream) => stream,
+        }
+    }
+
+    fn is_ready(&self) -> bool {
+        matches!(self, Conn::Ready(_))
+    }
+
+    /// Drives one more step of the handshake, transitioning to `Ready` if it
+    /// completes
+    fn advance(self) -> Result<Self, Box<dyn Error>> {
+        let mid = match self {
+            Conn::Ready(stream) => return Ok(Conn::Ready(stream)),
+            Conn::Handshaking(mid) => mid,
+        };
+
+        match mid.handshake() {
+            Ok(stream) => Ok(Conn::Ready(stream)),
+            Err(HandshakeError::WouldBlock(mid)) => Ok(Conn::Handshaking(mid)),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+}
+
+/// Benchmark harness for OpenSSL, driven over the same in-memory
+/// [`ConnectedBuffer`] pair used by [`crate::s2n_tls::S2NHarness`] so the two
+/// backends pay for identical IO plumbing
+pub struct OpenSslHarness {
+    // `Option` only to allow moving the current `Conn` out across the
+    // handshake/ready transition in `advance`; always `Some` once `new` returns
+    client: Option<Conn>,
+    server: Option<Conn>,
+}
+
+impl OpenSslHarness {
+    fn map_cipher_suite(cipher_suite: CipherSuite) -> &'static str {
+        match cipher_suite {
+            CipherSuite::AES_128_GCM_SHA256 => "TLS_AES_128_GCM_SHA256",
+            CipherSuite::AES_256_GCM_SHA384 => "TLS_AES_256_GCM_SHA384",
+            CipherSuite::CHACHA20_POLY1305_SHA256 => "TLS_CHACHA20_POLY1305_SHA256",
+        }
+    }
+
+    fn map_ec_group(ec_group: ECGroup) -> &'static str {
+        match ec_group {
+            ECGroup::SECP256R1 => "P-256",
+            ECGroup::X25519 => "X25519",
+            ECGroup::X25519MlKem768 | ECGroup::SecP256r1MlKem768 => {
+                panic!("post-quantum hybrid groups are not supported by this backend's OpenSSL build")
+            }
+        }
+    }
+
+    fn create_common_ctx_builder(
+        crypto_config: CryptoConfig,
+        keylog: Option<KeyLogSink>,
+    ) -> Result<SslContextBuilder, Box<dyn Error>> {
+        let mut builder = SslContext::builder(SslMethod::tls())?;
+        builder.set_min_proto_version(Some(SslVersion::TLS1_3))?;
+        builder.set_ciphersuites(Self::map_cipher_suite(crypto_config.cipher_suite))?;
+        builder.set_groups_list(Self::map_ec_group(crypto_config.ec_group))?;
+
+        if let Some(sink) = keylog {
+            builder.set_keylog_callback(move |_ssl, line| {
+                let mut sink = sink.lock().unwrap();
+                sink.write_all(line.as_bytes())
+                    .and_then(|_| sink.write_all(b"\n"))
+                    .ok();
+            });
+        }
+
+        Ok(builder)
+    }
+
+    fn load_cert_chain(
+        builder: &mut SslContextBuilder,
+        chain_pem: &[u8],
+        key_pem: &[u8],
+    ) -> Result<(), Box<dyn Error>> {
+        let mut chain = X509::stack_from_pem(chain_pem)?.into_iter();
+        builder.set_certificate(&chain.next().expect("empty certificate chain"))?;
+        for cert in chain {
+            builder.add_extra_chain_cert(cert)?;
+        }
+        builder.set_private_key(&PKey::private_key_from_pem(key_pem)?)?;
+        Ok(())
+    }
+
+    fn create_client_ctx(
+        crypto_config: CryptoConfig,
+        handshake_type: HandshakeType,
+        keylog: Option<KeyLogSink>,
+    ) -> Result<SslContext, Box<dyn Error>> {
+        let mut builder = Self::create_common_ctx_builder(crypto_config, keylog)?;
+        builder
+            .cert_store_mut()
+            .add_cert(X509::from_pem(&read_to_bytes(CACert, crypto_config.sig_type))?)?;
+        builder.set_verify(SslVerifyMode::PEER);
+
+        if handshake_type == HandshakeType::MutualAuth {
+            Self::load_cert_chain(
+                &mut builder,
+                &read_to_bytes(ClientCertChain, crypto_config.sig_type),
+                &read_to_bytes(ClientKey, crypto_config.sig_type),
+            )?;
+        }
+
+        Ok(builder.build())
+    }
+
+    fn create_server_ctx(
+        crypto_config: CryptoConfig,
+        handshake_type: HandshakeType,
+        keylog: Option<KeyLogSink>,
+    ) -> Result<SslContext, Box<dyn Error>> {
+        let mut builder = Self::create_common_ctx_builder(crypto_config, keylog)?;
+        Self::load_cert_chain(
+            &mut builder,
+            &read_to_bytes(ServerCertChain, crypto_config.sig_type),
+            &read_to_bytes(ServerKey, crypto_config.sig_type),
+        )?;
+
+        if handshake_type == HandshakeType::MutualAuth {
+            builder
+                .cert_store_mut()
+                .add_cert(X509::from_pem(&read_to_bytes(CACert, crypto_config.sig_type))?)?;
+            builder.set_verify(SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT);
+        }
+
+        Ok(builder.build())
+    }
+
+    fn start(ctx: &SslContext, buffer: ConnectedBuffer, mode: Mode) -> Result<Conn, Box<dyn Error>> {
+        let ssl = Ssl::new(ctx)?;
+        let result = match mode {
+            Mode::Client => ssl.connect(buffer),
+            Mode::Server => ssl.accept(buffer),
+        };
+
+        match result {
+            Ok(stream) => Ok(Conn::Ready(stream)),
+            Err(HandshakeError::WouldBlock(mid)) => Ok(Conn::Handshaking(mid)),
+            Err(err) => Err(Box::new(err)),
+        }
+    }
+}
+
+impl OpenSslHarness {
+    fn new_inner(
+        crypto_config: CryptoConfig,
+        handshake_type: HandshakeType,
+        buffer: ConnectedBuffer,
+        keylog: Option<KeyLogSink>,
+    ) -> Result<Self, Box<dyn Error>> {
+        if crypto_config.ocsp {
+            return Err("OCSP stapling is not implemented for the OpenSSL backend".into());
+        }
+
+        // `clone_inverse` shares the underlying queues with `client_buf`, swapped,
+        // so writes on one side are reads on the other
+        let client_buf = buffer;
+        let server_buf = client_buf.clone_inverse();
+
+        let client_ctx = Self::create_client_ctx(crypto_config, handshake_type, keylog.clone())?;
+        let server_ctx = Self::create_server_ctx(crypto_config, handshake_type, keylog)?;
+
+        let client = Self::start(&client_ctx, client_buf, Mode::Client)?;
+        let server = Self::start(&server_ctx, server_buf, Mode::Server)?;
+
+        Ok(Self {
+            client: Some(client),
+            server: Some(server),
+        })
+    }
+}
+
+impl TlsBenchHarness for OpenSslHarness {
+    fn new(
+        crypto_config: CryptoConfig,
+        handshake_type: HandshakeType,
+        buffer: ConnectedBuffer,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::new_inner(crypto_config, handshake_type, buffer, None)
+    }
+
+    fn new_with_keylog(
+        crypto_config: CryptoConfig,
+        handshake_type: HandshakeType,
+        buffer: ConnectedBuffer,
+        keylog: KeyLogSink,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::new_inner(crypto_config, handshake_type, buffer, Some(keylog))
+    }
+
+    fn handshake(&mut self) -> Result<(), Box<dyn Error>> {
+        for _ in 0..2 {
+            let client = self.client.take().expect("connection already taken");
+            self.client = Some(client.advance()?);
+            let server = self.server.take().expect("connection already taken");
+            self.server = Some(server.advance()?);
+        }
+        Ok(())
+    }
+
+    fn handshake_completed(&self) -> bool {
+        self.client.as_ref().is_some_and(Conn::is_ready)
+            && self.server.as_ref().is_some_and(Conn::is_ready)
+    }
+
+    fn get_negotiated_cipher_suite(&self) -> CipherSuite {
+        let name = self
+            .client
+            .as_ref()
+            .expect("handshake not complete")
+            .stream()
+            .ssl()
+            .current_cipher()
+            .expect("handshake not complete")
+            .standard_name()
+            .unwrap_or("Unknown cipher suite");
+
+        match name {
+            "TLS_AES_128_GCM_SHA256" => CipherSuite::AES_128_GCM_SHA256,
+            "TLS_AES_256_GCM_SHA384" => CipherSuite::AES_256_GCM_SHA384,
+            "TLS_CHACHA20_POLY1305_SHA256" => CipherSuite::CHACHA20_POLY1305_SHA256,
+            _ => panic!("Unknown cipher suite"),
+        }
+    }
+
+    fn get_negotiated_group(&self) -> ECGroup {
+        match self
+            .client
+            .as_ref()
+            .expect("handshake not complete")
+            .stream()
+            .ssl()
+            .group_name()
+            .expect("handshake not complete")
+        {
+            "P-256" => ECGroup::SECP256R1,
+            "X25519" => ECGroup::X25519,
+            _ => panic!("Unknown negotiated group"),
+        }
+    }
+
+    fn negotiated_tls13(&self) -> bool {
+        self.client
+            .as_ref()
+            .expect("handshake not complete")
+            .stream()
+            .ssl()
+            .version_str()
+            == "TLSv1.3"
+    }
+
+    fn send(&mut self, sender: Mode, data: &[u8]) -> Result<(), Box<dyn Error>> {
+        let conn = match sender {
+            Mode::Client => &mut self.client,
+            Mode::Server => &mut self.server,
+        };
+
+        match conn.as_mut().expect("connection already taken") {
+            Conn::Ready(stream) => {
+                stream.write_all(data)?;
+                stream.flush()?;
+                Ok(())
+            }
+            Conn::Handshaking(_) => panic!("handshake not complete"),
+        }
+    }
+
+    fn recv(&mut self, receiver: Mode, data: &mut [u8]) -> Result<(), Box<dyn Error>> {
+        use std::io::Read;
+
+        let conn = match receiver {
+            Mode::Client => &mut self.client,
+            Mode::Server => &mut self.server,
+        };
+
+        match conn.as_mut().expect("connection already taken") {
+            Conn::Ready(stream) => {
+                stream.read_exact(data)?;
+                Ok(())
+            }
+            Conn::Handshaking(_) => panic!("handshake not complete"),
+        }
+    }
+}
@@ -3,13 +3,21 @@
 
 use crate::{
     harness::{
-        read_to_bytes, CipherSuite, ConnectedBuffer, CryptoConfig, ECGroup, HandshakeType, Mode,
-        TlsBenchHarness,
+        read_to_bytes, CipherSuite, ConnectedBuffer, CryptoConfig, ECGroup, HandshakeType,
+        KeyLogSink, Mode, TlsBenchHarness,
     },
     PemType::*,
+    SigType,
+};
+use openssl::{
+    hash::MessageDigest,
+    ocsp::{OcspCertId, OcspCertStatus, OcspResponse as OpensslOcspResponse},
+    x509::X509,
 };
 use s2n_tls::{
-    callbacks::VerifyHostNameCallback,
+    callbacks::{
+        KeyLogCallback, OcspResponseCallback, SessionTicketCallback, VerifyHostNameCallback,
+    },
     config::{Builder, Config},
     connection::Connection,
     enums::{Blinding, ClientAuthType, Version},
@@ -21,9 +29,85 @@ use std::{
     io::{ErrorKind, Read, Write},
     os::raw::c_int,
     pin::Pin,
+    sync::{Arc, Mutex},
     task::Poll::Ready,
+    time::SystemTime,
 };
 
+/// Key used to encrypt/decrypt session tickets for the resumption benchmarks.
+/// The value doesn't matter since keys are never shared outside this process.
+const SESSION_TICKET_KEY_NAME: &[u8] = b"s2n-tls-bench-ticket-key";
+const SESSION_TICKET_KEY: &[u8] = &[0; 16];
+
+/// Stores the session ticket handed to the client so a later handshake can
+/// be resumed from it
+#[derive(Clone, Default)]
+struct StoredSessionTicket(Arc<Mutex<Option<Vec<u8>>>>);
+
+impl SessionTicketCallback for StoredSessionTicket {
+    fn on_session_ticket(
+        &self,
+        _connection: &mut Connection,
+        session_ticket: &s2n_tls::connection::SessionTicket,
+    ) {
+        let mut ticket = vec![0; session_ticket.len().unwrap()];
+        session_ticket.data(&mut ticket).unwrap();
+        *self.0.lock().unwrap() = Some(ticket);
+    }
+}
+
+/// Runs as part of certificate verification during the handshake (not after
+/// the fact) and fails negotiation unless the server stapled an OCSP
+/// response whose `BasicOCSPResponse.tbsResponseData.responses[0].certStatus`
+/// is `good` for this benchmark's server certificate. This is what makes the
+/// OCSP benchmark actually pay for parsing the stapled response, rather than
+/// just checking the outer `OCSPResponseStatus` (which is `successful` even
+/// when the per-certificate status it carries is `revoked`).
+struct OcspVerifier {
+    cert_id: OcspCertId,
+}
+
+impl OcspVerifier {
+    fn new(sig_type: SigType) -> Result<Self, Box<dyn Error>> {
+        let subject = X509::stack_from_pem(&read_to_bytes(ServerCertChain, sig_type))?
+            .into_iter()
+            .next()
+            .expect("empty server certificate chain");
+        let issuer = X509::from_pem(&read_to_bytes(CACert, sig_type))?;
+        let cert_id = OcspCertId::from_cert(MessageDigest::sha1(), &subject, &issuer)?;
+
+        Ok(Self { cert_id })
+    }
+}
+
+impl OcspResponseCallback for OcspVerifier {
+    fn verify_ocsp_response(&self, response: &[u8]) -> bool {
+        let Ok(response) = OpensslOcspResponse::from_der(response) else {
+            return false;
+        };
+        let Ok(basic) = response.basic() else {
+            return false;
+        };
+
+        basic
+            .find_status(&self.cert_id)
+            .is_some_and(|status| status.status == OcspCertStatus::GOOD)
+    }
+}
+
+/// Appends NSS-format `SSLKEYLOGFILE` lines to a shared sink as s2n-tls
+/// derives each secret, so bench traffic can be decrypted in Wireshark
+struct KeyLogHandler {
+    sink: KeyLogSink,
+}
+
+impl KeyLogCallback for KeyLogHandler {
+    fn on_key_log(&self, _connection: &Connection, line: &[u8]) {
+        let mut sink = self.sink.lock().unwrap();
+        sink.write_all(line).and_then(|_| sink.write_all(b"\n")).ok();
+    }
+}
+
 #[allow(dead_code)]
 pub struct S2NHarness {
     // UnsafeCell is needed b/c client and server share *mut to IO buffers
@@ -34,6 +118,7 @@ pub struct S2NHarness {
     server_conn: Connection,
     client_handshake_completed: bool,
     server_handshake_completed: bool,
+    session_ticket: StoredSessionTicket,
 }
 
 /// Custom callback for verifying hostnames. Rustls requires checking hostnames,
@@ -85,6 +170,23 @@ impl S2NHarness {
             (CipherSuite::AES_256_GCM_SHA384, ECGroup::SECP256R1) => "20190802",
             (CipherSuite::AES_128_GCM_SHA256, ECGroup::X25519) => "default_tls13",
             (CipherSuite::AES_256_GCM_SHA384, ECGroup::X25519) => "20190801",
+            (CipherSuite::CHACHA20_POLY1305_SHA256, ECGroup::SECP256R1)
+            | (CipherSuite::CHACHA20_POLY1305_SHA256, ECGroup::X25519) => {
+                "CloudFront-TLS-1-2-2021-ChaCha20-Boosted"
+            }
+            (CipherSuite::AES_128_GCM_SHA256, ECGroup::X25519MlKem768)
+            | (CipherSuite::AES_128_GCM_SHA256, ECGroup::SecP256r1MlKem768)
+            | (CipherSuite::AES_256_GCM_SHA384, ECGroup::X25519MlKem768)
+            | (CipherSuite::AES_256_GCM_SHA384, ECGroup::SecP256r1MlKem768) => {
+                "PQ-TLS-1-3-2023-06-01"
+            }
+            (CipherSuite::CHACHA20_POLY1305_SHA256, ECGroup::X25519MlKem768)
+            | (CipherSuite::CHACHA20_POLY1305_SHA256, ECGroup::SecP256r1MlKem768) => {
+                return Err(
+                    "no security policy pairs CHACHA20_POLY1305_SHA256 with a post-quantum hybrid group"
+                        .into(),
+                )
+            }
         };
 
         let mut builder = Builder::new();
@@ -92,7 +194,7 @@ impl S2NHarness {
             .set_security_policy(&Policy::from_version(security_policy)?)?
             .wipe_trust_store()?
             .set_client_auth_type(match handshake_type {
-                HandshakeType::ServerAuth => ClientAuthType::None,
+                HandshakeType::ServerAuth | HandshakeType::Resumption => ClientAuthType::None,
                 HandshakeType::MutualAuth => ClientAuthType::Required,
             })?;
 
@@ -102,6 +204,8 @@ impl S2NHarness {
     fn create_client_config(
         crypto_config: CryptoConfig,
         handshake_type: HandshakeType,
+        session_ticket: StoredSessionTicket,
+        keylog: Option<KeyLogSink>,
     ) -> Result<Config, Box<dyn Error>> {
         let mut builder = Self::create_common_config_builder(crypto_config, handshake_type)?;
         builder
@@ -117,12 +221,27 @@ impl S2NHarness {
             )?;
         }
 
+        if handshake_type == HandshakeType::Resumption {
+            builder.set_session_ticket_callback(session_ticket)?;
+        }
+
+        if crypto_config.ocsp {
+            builder
+                .enable_ocsp_stapling()?
+                .set_ocsp_response_callback(OcspVerifier::new(crypto_config.sig_type)?)?;
+        }
+
+        if let Some(sink) = keylog {
+            builder.set_key_log_callback(KeyLogHandler { sink })?;
+        }
+
         Ok(builder.build()?)
     }
 
     fn create_server_config(
         crypto_config: CryptoConfig,
         handshake_type: HandshakeType,
+        keylog: Option<KeyLogSink>,
     ) -> Result<Config, Box<dyn Error>> {
         let mut builder = Self::create_common_config_builder(crypto_config, handshake_type)?;
         builder.load_pem(
@@ -138,6 +257,24 @@ impl S2NHarness {
                 })?;
         }
 
+        if handshake_type == HandshakeType::Resumption {
+            builder
+                .add_session_ticket_key(
+                    SESSION_TICKET_KEY_NAME,
+                    SESSION_TICKET_KEY,
+                    SystemTime::now(),
+                )?
+                .enable_session_tickets(true)?;
+        }
+
+        if crypto_config.ocsp {
+            builder.set_ocsp_data(&read_to_bytes(OcspResponse, crypto_config.sig_type))?;
+        }
+
+        if let Some(sink) = keylog {
+            builder.set_key_log_callback(KeyLogHandler { sink })?;
+        }
+
         Ok(builder.build()?)
     }
 
@@ -174,19 +311,70 @@ impl S2NHarness {
         }
         Ok(())
     }
+
+    /// Untimed setup for a PSK-resumed handshake benchmark: runs a full
+    /// handshake to let the server issue a session ticket, tears the
+    /// connections down while keeping the ticket, then builds a fresh
+    /// client `Connection` seeded with that ticket. Negotiation is *not*
+    /// started — call `.handshake()` on the result to run (and time) just
+    /// the PSK-resumed handshake, mirroring the untimed-`new`/timed-
+    /// `handshake` split every other harness construction follows. Use
+    /// [`Self::resumed`] afterwards to confirm the handshake actually
+    /// resumed rather than falling back to a full handshake.
+    pub fn new_for_resumption(
+        crypto_config: CryptoConfig,
+        buffer: ConnectedBuffer,
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut initial = Self::new(crypto_config, HandshakeType::Resumption, buffer)?;
+        initial.handshake()?;
+        assert!(initial.handshake_completed());
+
+        let ticket = initial
+            .session_ticket
+            .0
+            .lock()
+            .unwrap()
+            .take()
+            .expect("server did not issue a session ticket during the full handshake");
+
+        let mut resumed = Self::new(
+            crypto_config,
+            HandshakeType::Resumption,
+            ConnectedBuffer::new(),
+        )?;
+        resumed.client_conn.set_session_ticket(&ticket)?;
+
+        Ok(resumed)
+    }
+
+    /// Returns true if the handshake negotiated a PSK from a stored session
+    /// ticket rather than performing a full handshake
+    pub fn resumed(&self) -> bool {
+        self.client_conn
+            .handshake_type()
+            .map(|name| name.contains("NEGOTIATED") && !name.contains("FULL_HANDSHAKE"))
+            .unwrap_or(false)
+    }
 }
 
-impl TlsBenchHarness for S2NHarness {
-    fn new(
+impl S2NHarness {
+    fn new_inner(
         crypto_config: CryptoConfig,
         handshake_type: HandshakeType,
         buffer: ConnectedBuffer,
+        keylog: Option<KeyLogSink>,
     ) -> Result<Self, Box<dyn Error>> {
         let mut client_buf = Box::pin(buffer);
         let mut server_buf = Box::pin(client_buf.clone_inverse());
 
-        let client_config = Self::create_client_config(crypto_config, handshake_type)?;
-        let server_config = Self::create_server_config(crypto_config, handshake_type)?;
+        let session_ticket = StoredSessionTicket::default();
+        let client_config = Self::create_client_config(
+            crypto_config,
+            handshake_type,
+            session_ticket.clone(),
+            keylog.clone(),
+        )?;
+        let server_config = Self::create_server_config(crypto_config, handshake_type, keylog)?;
 
         let mut client_conn = Connection::new_client();
         let mut server_conn = Connection::new_server();
@@ -201,10 +389,30 @@ impl TlsBenchHarness for S2NHarness {
             server_conn,
             client_handshake_completed: false,
             server_handshake_completed: false,
+            session_ticket,
         };
 
         Ok(harness)
     }
+}
+
+impl TlsBenchHarness for S2NHarness {
+    fn new(
+        crypto_config: CryptoConfig,
+        handshake_type: HandshakeType,
+        buffer: ConnectedBuffer,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::new_inner(crypto_config, handshake_type, buffer, None)
+    }
+
+    fn new_with_keylog(
+        crypto_config: CryptoConfig,
+        handshake_type: HandshakeType,
+        buffer: ConnectedBuffer,
+        keylog: KeyLogSink,
+    ) -> Result<Self, Box<dyn Error>> {
+        Self::new_inner(crypto_config, handshake_type, buffer, Some(keylog))
+    }
 
     fn handshake(&mut self) -> Result<(), Box<dyn Error>> {
         for _ in 0..2 {
@@ -222,10 +430,27 @@ impl TlsBenchHarness for S2NHarness {
         match self.client_conn.cipher_suite().unwrap() {
             "TLS_AES_128_GCM_SHA256" => CipherSuite::AES_128_GCM_SHA256,
             "TLS_AES_256_GCM_SHA384" => CipherSuite::AES_256_GCM_SHA384,
+            "TLS_CHACHA20_POLY1305_SHA256" => CipherSuite::CHACHA20_POLY1305_SHA256,
             _ => panic!("Unknown cipher suite"),
         }
     }
 
+    fn get_negotiated_group(&self) -> ECGroup {
+        if let Some(kem_group) = self.client_conn.kem_group_name().unwrap() {
+            return match kem_group {
+                "X25519MLKEM768" => ECGroup::X25519MlKem768,
+                "SecP256r1MLKEM768" => ECGroup::SecP256r1MlKem768,
+                _ => panic!("Unknown negotiated KEM group"),
+            };
+        }
+
+        match self.client_conn.curve().unwrap() {
+            "secp256r1" => ECGroup::SECP256R1,
+            "x25519" => ECGroup::X25519,
+            _ => panic!("Unknown negotiated group"),
+        }
+    }
+
     fn negotiated_tls13(&self) -> bool {
         self.client_conn.actual_protocol_version().unwrap() == Version::TLS13
     }